@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+use sha2::{Digest, Sha256};
+
+use crate::diagnostic::Diagnostic;
+
+/// Hashes a single statement's source text so it can be used as a cache key.
+/// Two statements with byte-identical text always hash the same, regardless
+/// of where in the script they appear.
+pub fn hash_statement(source: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(source.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// An on-disk, SQLite-backed memo of lint results, keyed by statement hash.
+/// Modeled on the `nml` project's `cache` module: before re-running the
+/// linter set on a statement, look up its hash here; on a hit, reuse the
+/// stored diagnostics instead of re-linting.
+pub struct LintCache {
+    conn: Connection,
+}
+
+impl LintCache {
+    pub fn open(path: &Path) -> rusqlite::Result<Self> {
+        let conn = Connection::open(path)?;
+        conn.execute(
+            "CREATE TABLE IF NOT EXISTS lint_cache (
+                statement_hash TEXT PRIMARY KEY,
+                diagnostics_json TEXT NOT NULL
+            )",
+            [],
+        )?;
+        Ok(LintCache { conn })
+    }
+
+    /// Looks up the cached diagnostics for a statement hash. Returns `None`
+    /// on a cache miss, and also on a hit whose JSON payload fails to
+    /// deserialize (e.g. written by an older, incompatible version).
+    pub fn get(&self, statement_hash: &str) -> rusqlite::Result<Option<Vec<Diagnostic>>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT diagnostics_json FROM lint_cache WHERE statement_hash = ?1")?;
+        let mut rows = stmt.query(params![statement_hash])?;
+        match rows.next()? {
+            Some(row) => {
+                let diagnostics_json: String = row.get(0)?;
+                Ok(serde_json::from_str(&diagnostics_json).ok())
+            }
+            None => Ok(None),
+        }
+    }
+
+    pub fn put(&self, statement_hash: &str, diagnostics: &[Diagnostic]) -> rusqlite::Result<()> {
+        let diagnostics_json = serde_json::to_string(diagnostics)
+            .expect("Diagnostic serialization is infallible");
+        self.conn.execute(
+            "INSERT OR REPLACE INTO lint_cache (statement_hash, diagnostics_json) VALUES (?1, ?2)",
+            params![statement_hash, diagnostics_json],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::diagnostic::Diagnostic;
+
+    #[test]
+    fn hash_statement_is_stable_and_content_sensitive() {
+        assert_eq!(hash_statement("CREATE TABLE foo (a Date)"), hash_statement("CREATE TABLE foo (a Date)"));
+        assert_ne!(hash_statement("CREATE TABLE foo (a Date)"), hash_statement("CREATE TABLE bar (a Date)"));
+    }
+
+    #[test]
+    fn get_returns_none_on_a_cache_miss() {
+        let cache = LintCache::open(Path::new(":memory:")).unwrap();
+        assert_eq!(cache.get("does-not-exist").unwrap(), None);
+    }
+
+    #[test]
+    fn put_then_get_round_trips_the_stored_diagnostics() {
+        let cache = LintCache::open(Path::new(":memory:")).unwrap();
+        let diagnostics = vec![Diagnostic::new("oops", 5).with_suggestion(5..8, "fix")];
+
+        cache.put("abc123", &diagnostics).unwrap();
+
+        assert_eq!(cache.get("abc123").unwrap(), Some(diagnostics));
+    }
+
+    #[test]
+    fn put_overwrites_a_previous_entry_for_the_same_hash() {
+        let cache = LintCache::open(Path::new(":memory:")).unwrap();
+        cache.put("abc123", &[Diagnostic::new("first", 0)]).unwrap();
+        cache.put("abc123", &[Diagnostic::new("second", 1)]).unwrap();
+
+        let cached = cache.get("abc123").unwrap().unwrap();
+        assert_eq!(cached.len(), 1);
+        assert_eq!(cached[0].message, "second");
+    }
+}
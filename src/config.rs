@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::diagnostic::Severity;
+use crate::linter::{DuplicateColNamesLint, DuplicateTableNamesLint, Lint, TableNameLengthLint};
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_severity() -> Severity {
+    Severity::Error
+}
+
+fn default_min_name_length() -> usize {
+    5
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct RuleConfig {
+    pub enabled: bool,
+    pub severity: Severity,
+}
+
+impl Default for RuleConfig {
+    fn default() -> Self {
+        RuleConfig {
+            enabled: default_true(),
+            severity: default_severity(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct TableNameLengthConfig {
+    pub enabled: bool,
+    pub severity: Severity,
+    pub min_name_length: usize,
+}
+
+impl Default for TableNameLengthConfig {
+    fn default() -> Self {
+        TableNameLengthConfig {
+            enabled: default_true(),
+            severity: default_severity(),
+            min_name_length: default_min_name_length(),
+        }
+    }
+}
+
+/// The `clicklint.toml` schema: one table per lint, keyed by its id, that
+/// selects whether the lint runs and tunes its parameters. Analogous to how
+/// `ruff`/`pyproject-fmt` are driven by a TOML table of per-rule options.
+#[derive(Debug, Deserialize, Default)]
+#[serde(default)]
+pub struct Config {
+    #[serde(rename = "duplicate-col-names")]
+    pub duplicate_col_names: RuleConfig,
+    #[serde(rename = "table-name-length")]
+    pub table_name_length: TableNameLengthConfig,
+    #[serde(rename = "duplicate-table-names")]
+    pub duplicate_table_names: RuleConfig,
+}
+
+impl Config {
+    /// Loads `clicklint.toml` from `path`, falling back to defaults (every
+    /// lint enabled at its default severity) if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Config, Box<dyn std::error::Error + Send + Sync>> {
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Ok(toml::from_str(&contents)?),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Builds the active set of linters from this configuration, in a
+    /// fixed, stable order.
+    pub fn build_lints(&self) -> Vec<Box<dyn Lint>> {
+        vec![
+            Box::new(DuplicateColNamesLint {
+                enabled: self.duplicate_col_names.enabled,
+                severity: self.duplicate_col_names.severity,
+            }),
+            Box::new(TableNameLengthLint {
+                enabled: self.table_name_length.enabled,
+                severity: self.table_name_length.severity,
+                min_name_length: self.table_name_length.min_name_length,
+            }),
+            Box::new(DuplicateTableNamesLint {
+                enabled: self.duplicate_table_names.enabled,
+                severity: self.duplicate_table_names.severity,
+            }),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_config_enables_every_lint_at_error_severity() {
+        let config = Config::default();
+        assert!(config.duplicate_col_names.enabled);
+        assert_eq!(config.duplicate_col_names.severity, Severity::Error);
+        assert!(config.table_name_length.enabled);
+        assert_eq!(config.table_name_length.severity, Severity::Error);
+        assert!(config.duplicate_table_names.enabled);
+        assert_eq!(config.duplicate_table_names.severity, Severity::Error);
+        assert_eq!(config.table_name_length.min_name_length, 5);
+    }
+
+    #[test]
+    fn parses_a_sample_clicklint_toml() {
+        let toml = r#"
+            [duplicate-col-names]
+            enabled = false
+
+            [table-name-length]
+            severity = "warning"
+            min_name_length = 10
+
+            [duplicate-table-names]
+            severity = "warn"
+        "#;
+
+        let config: Config = toml::from_str(toml).unwrap();
+        assert!(!config.duplicate_col_names.enabled);
+        assert_eq!(config.duplicate_col_names.severity, Severity::Error);
+        assert_eq!(config.table_name_length.severity, Severity::Warn);
+        assert_eq!(config.table_name_length.min_name_length, 10);
+        assert_eq!(config.duplicate_table_names.severity, Severity::Warn);
+        assert!(config.duplicate_table_names.enabled);
+    }
+
+    #[test]
+    fn load_falls_back_to_defaults_when_the_file_is_missing() {
+        let config = Config::load(Path::new("does-not-exist-clicklint.toml")).unwrap();
+        assert!(config.duplicate_col_names.enabled);
+        assert_eq!(config.table_name_length.min_name_length, 5);
+    }
+}
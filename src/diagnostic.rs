@@ -0,0 +1,214 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use crate::span::format_location;
+
+/// How seriously a diagnostic should be treated. Set on a `Diagnostic` by
+/// the `Lint` that produced it, based on the lint's configured severity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    #[serde(alias = "warning")]
+    Warn,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Error => write!(f, "error"),
+            Severity::Warn => write!(f, "warning"),
+        }
+    }
+}
+
+/// A single correctable edit into the original source text.
+///
+/// `byte_range` is a span into the text that was passed to the parser, and
+/// `replacement` is the text that should take its place. Suggestions are
+/// only ever additive metadata on a [`Diagnostic`]; a diagnostic with no
+/// suggestions is still reported, it just can't be auto-fixed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Suggestion {
+    pub byte_range: Range<usize>,
+    pub replacement: String,
+}
+
+/// The result of running a single linter against a single table, or against
+/// the whole script for a cross-statement lint.
+///
+/// Modeled after `rustfix`: a human-readable `message` plus zero or more
+/// [`Suggestion`]s that, if applied, would resolve the issue. `location` is
+/// the byte offset into the source that the message is about, so callers
+/// can report `file:line:col: message` instead of a bare string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Diagnostic {
+    pub message: String,
+    pub location: usize,
+    pub severity: Severity,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic defaulting to [`Severity::Error`]; linters that
+    /// are wired up through a [`crate::linter::Lint`] have their configured
+    /// severity stamped on afterwards via [`Diagnostic::with_severity`].
+    pub fn new(message: impl Into<String>, location: usize) -> Self {
+        Diagnostic {
+            message: message.into(),
+            location,
+            severity: Severity::Error,
+            suggestions: Vec::new(),
+        }
+    }
+
+    // No lint attaches a suggestion yet, but `apply_suggestions` and `--fix`
+    // are already built to consume them once one does.
+    #[allow(dead_code)]
+    pub fn with_suggestion(mut self, byte_range: Range<usize>, replacement: impl Into<String>) -> Self {
+        self.suggestions.push(Suggestion {
+            byte_range,
+            replacement: replacement.into(),
+        });
+        self
+    }
+
+    pub fn with_severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Renders this diagnostic as `file:line:col: severity: message`.
+    pub fn format(&self, file: &str, source: &str) -> String {
+        format_location(file, source, self.location, &format!("{}: {}", self.severity, self.message))
+    }
+
+    /// Applies `shift` to this diagnostic's `location` and every
+    /// suggestion's `byte_range`. Used to move a diagnostic between a
+    /// statement-relative view (offset 0 == the statement's own start) and
+    /// the absolute view (offset 0 == the start of the whole script), the
+    /// same way `parser::parse_script` rebases parser spans.
+    fn rebase(&self, shift: impl Fn(usize) -> usize) -> Diagnostic {
+        Diagnostic {
+            message: self.message.clone(),
+            location: shift(self.location),
+            severity: self.severity,
+            suggestions: self
+                .suggestions
+                .iter()
+                .map(|s| Suggestion {
+                    byte_range: shift(s.byte_range.start)..shift(s.byte_range.end),
+                    replacement: s.replacement.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Converts diagnostics produced for one statement (absolute offsets into
+/// the whole script) into a position-independent form, relative to that
+/// statement's own start. This is what the lint cache stores, so a cache
+/// hit is valid regardless of where the statement's text later appears.
+pub fn to_relative(diagnostics: &[Diagnostic], statement_start: usize) -> Vec<Diagnostic> {
+    diagnostics.iter().map(|d| d.rebase(|offset| offset - statement_start)).collect()
+}
+
+/// The inverse of [`to_relative`]: rebases statement-relative diagnostics
+/// (as read back from the cache) onto the statement's current absolute
+/// position in the script being linted.
+pub fn to_absolute(diagnostics: &[Diagnostic], statement_start: usize) -> Vec<Diagnostic> {
+    diagnostics.iter().map(|d| d.rebase(|offset| offset + statement_start)).collect()
+}
+
+/// Applies the suggestions carried by `diagnostics` to `source`.
+///
+/// Suggestions are sorted by their starting offset. Any suggestion whose
+/// range overlaps a previously accepted suggestion is discarded, so
+/// conflicting fixes are skipped rather than corrupting the text. The
+/// surviving suggestions are then applied from the highest offset to the
+/// lowest so that earlier byte offsets remain valid as we go.
+pub fn apply_suggestions(source: &str, diagnostics: &[Diagnostic]) -> String {
+    let mut suggestions: Vec<&Suggestion> = diagnostics
+        .iter()
+        .flat_map(|d| d.suggestions.iter())
+        .collect();
+    suggestions.sort_by_key(|s| s.byte_range.start);
+
+    let mut accepted: Vec<&Suggestion> = Vec::new();
+    let mut last_end = 0usize;
+    for suggestion in suggestions {
+        if suggestion.byte_range.start < last_end {
+            // Overlaps the previously accepted suggestion; skip it.
+            continue;
+        }
+        last_end = suggestion.byte_range.end;
+        accepted.push(suggestion);
+    }
+
+    let mut fixed = source.to_string();
+    for suggestion in accepted.iter().rev() {
+        fixed.replace_range(suggestion.byte_range.clone(), &suggestion.replacement);
+    }
+    fixed
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn severity_deserializes_warn_and_its_warning_alias() {
+        assert_eq!(serde_json::from_str::<Severity>("\"warn\"").unwrap(), Severity::Warn);
+        assert_eq!(serde_json::from_str::<Severity>("\"warning\"").unwrap(), Severity::Warn);
+        assert_eq!(serde_json::from_str::<Severity>("\"error\"").unwrap(), Severity::Error);
+    }
+
+    #[test]
+    fn skips_overlapping_suggestions() {
+        let source = "abcdef".to_string();
+        let diagnostics = vec![
+            Diagnostic::new("first", 0).with_suggestion(0..3, "XYZ"),
+            Diagnostic::new("second", 2).with_suggestion(2..4, "!!"),
+        ];
+        assert_eq!(apply_suggestions(&source, &diagnostics), "XYZdef");
+    }
+
+    #[test]
+    fn applies_non_overlapping_suggestions_in_order() {
+        let source = "abcdef".to_string();
+        let diagnostics = vec![
+            Diagnostic::new("first", 0).with_suggestion(0..1, "A"),
+            Diagnostic::new("second", 4).with_suggestion(4..6, "FF"),
+        ];
+        assert_eq!(apply_suggestions(&source, &diagnostics), "AbcdFF");
+    }
+
+    #[test]
+    fn formats_with_line_and_column() {
+        let diagnostic = Diagnostic::new("oops", 3);
+        assert_eq!(diagnostic.format("schema.sql", "ab\ncd"), "schema.sql:2:1: error: oops");
+    }
+
+    #[test]
+    fn to_relative_and_to_absolute_round_trip_through_a_new_statement_offset() {
+        let absolute = vec![Diagnostic::new("dup", 25).with_suggestion(20..30, "fixed")];
+
+        let relative = to_relative(&absolute, 20);
+        assert_eq!(relative[0].location, 5);
+        assert_eq!(relative[0].suggestions[0].byte_range, 0..10);
+
+        // Rebasing onto a different statement start (as happens when the
+        // same statement text reappears at a new offset in the script)
+        // must land on the new absolute position, not the original one.
+        let rebased = to_absolute(&relative, 100);
+        assert_eq!(rebased[0].location, 105);
+        assert_eq!(rebased[0].suggestions[0].byte_range, 100..110);
+    }
+
+    #[test]
+    fn formats_with_configured_severity() {
+        let diagnostic = Diagnostic::new("oops", 0).with_severity(Severity::Warn);
+        assert_eq!(diagnostic.format("schema.sql", "ab\ncd"), "schema.sql:1:1: warning: oops");
+    }
+}
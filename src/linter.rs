@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::parser::{Col, Table};
+
+/// Flags columns that share a name within the same table. Returns one
+/// diagnostic per duplicated name, ordered by where the name first appears,
+/// so the output is deterministic (`HashMap` iteration order is not) and
+/// each group points at its own location instead of an arbitrarily chosen
+/// one.
+pub fn check_duplicate_col_names(t: &Table) -> Vec<Diagnostic> {
+    let mut col_names: HashMap<&str, Vec<&Col>> = HashMap::new();
+    for col in t.columns.iter() {
+        col_names.entry(col.name.as_str()).or_default().push(col);
+    }
+    let mut duplicate_groups: Vec<Vec<&Col>> = col_names
+        .into_values()
+        .filter(|cols| cols.len() > 1)
+        .collect();
+    duplicate_groups.sort_by_key(|cols| cols[0].span.start);
+
+    duplicate_groups
+        .into_iter()
+        .map(|cols| {
+            let last = cols.last().expect("duplicate group has at least 2 columns");
+            Diagnostic::new(
+                format!("Duplicated column {} was encountered {} times.", cols[0].name, cols.len()),
+                last.span.start,
+            )
+        })
+        .collect()
+}
+
+pub fn check_table_name_is_not_short(t: &Table, min_name_length: usize) -> Option<Diagnostic> {
+    if t.name.len() < min_name_length {
+        return Some(Diagnostic::new(
+            format!(
+                "Your table name '{}' is too short. We recommend at least {} characters.",
+                t.name, min_name_length
+            ),
+            t.span.start,
+        ));
+    }
+    None
+}
+
+/// Flags a table name that is explicitly defined more than once in the same
+/// script, the way a TOML parser errors on a table path that's already been
+/// explicitly defined. We track which names we've seen as we walk the
+/// statement list in order; a later statement guarded by `IF NOT EXISTS` is
+/// assumed to be defensive rather than a real redefinition, so it is not
+/// flagged.
+pub fn check_duplicate_table_names(tables: &[Table]) -> Vec<Diagnostic> {
+    let mut seen = HashSet::new();
+    let mut diagnostics = Vec::new();
+    for table in tables {
+        if seen.contains(&table.name) {
+            if !table.if_not_exists {
+                diagnostics.push(Diagnostic::new(
+                    format!(
+                        "Table '{}' is redefined here; it was already defined earlier in this script.",
+                        table.name
+                    ),
+                    table.span.start,
+                ));
+            }
+            continue;
+        }
+        seen.insert(table.name.clone());
+    }
+    diagnostics
+}
+
+/// A registered, individually configurable lint rule.
+///
+/// Each rule carries its own `id` (used as its key in `clicklint.toml`) and
+/// `severity`, plus whatever fields it needs to tune its behavior. Most
+/// rules only implement one of `check_table`/`check_script`, since most
+/// rules only care about a single statement at a time; the other is left as
+/// a no-op default.
+pub trait Lint {
+    fn id(&self) -> &'static str;
+    fn enabled(&self) -> bool;
+    fn severity(&self) -> Severity;
+
+    fn check_table(&self, _table: &Table) -> Vec<Diagnostic> {
+        Vec::new()
+    }
+
+    fn check_script(&self, _tables: &[Table]) -> Vec<Diagnostic> {
+        Vec::new()
+    }
+}
+
+/// Stamps each of `lint`'s diagnostics with its configured severity and
+/// appends its rule id (e.g. `[duplicate-col-names]`) to the message, so a
+/// report tells the reader which key to flip off in `clicklint.toml` if they
+/// want to silence it.
+fn finish(lint: &impl Lint, diagnostics: Vec<Diagnostic>) -> Vec<Diagnostic> {
+    diagnostics
+        .into_iter()
+        .map(|d| Diagnostic {
+            message: format!("{} [{}]", d.message, lint.id()),
+            ..d.with_severity(lint.severity())
+        })
+        .collect()
+}
+
+pub struct DuplicateColNamesLint {
+    pub enabled: bool,
+    pub severity: Severity,
+}
+
+impl Lint for DuplicateColNamesLint {
+    fn id(&self) -> &'static str {
+        "duplicate-col-names"
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check_table(&self, table: &Table) -> Vec<Diagnostic> {
+        finish(self, check_duplicate_col_names(table))
+    }
+}
+
+pub struct TableNameLengthLint {
+    pub enabled: bool,
+    pub severity: Severity,
+    pub min_name_length: usize,
+}
+
+impl Lint for TableNameLengthLint {
+    fn id(&self) -> &'static str {
+        "table-name-length"
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check_table(&self, table: &Table) -> Vec<Diagnostic> {
+        finish(
+            self,
+            check_table_name_is_not_short(table, self.min_name_length).into_iter().collect(),
+        )
+    }
+}
+
+pub struct DuplicateTableNamesLint {
+    pub enabled: bool,
+    pub severity: Severity,
+}
+
+impl Lint for DuplicateTableNamesLint {
+    fn id(&self) -> &'static str {
+        "duplicate-table-names"
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn check_script(&self, tables: &[Table]) -> Vec<Diagnostic> {
+        finish(self, check_duplicate_table_names(tables))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn col(name: &str, start: usize, end: usize) -> Col {
+        Col {
+            name: name.to_string(),
+            col_type: "Date".to_string(),
+            span: start..end,
+        }
+    }
+
+    fn table(name: &str, if_not_exists: bool, start: usize, end: usize) -> Table {
+        Table {
+            name: name.to_string(),
+            columns: Vec::new(),
+            if_not_exists,
+            span: start..end,
+        }
+    }
+
+    #[test]
+    fn reports_each_duplicate_group_at_its_own_location_in_first_seen_order() {
+        // Two separate duplicate groups; "a" first appears before "b" in the
+        // source, so "a" must be reported first whatever order the HashMap
+        // iterates in, which guards against the HashMap-iteration-order
+        // flakiness the unsorted version had.
+        let table = Table {
+            name: "widgets".to_string(),
+            columns: vec![
+                col("a", 0, 5),
+                col("b", 6, 11),
+                col("a", 12, 17),
+                col("b", 18, 23),
+            ],
+            if_not_exists: false,
+            span: 0..23,
+        };
+
+        let diagnostics = check_duplicate_col_names(&table);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].location, 12);
+        assert!(diagnostics[0].message.contains("a"));
+        assert_eq!(diagnostics[1].location, 18);
+        assert!(diagnostics[1].message.contains("b"));
+    }
+
+    #[test]
+    fn no_diagnostics_when_columns_are_unique() {
+        let table = Table {
+            name: "widgets".to_string(),
+            columns: vec![col("a", 0, 5), col("b", 6, 11)],
+            if_not_exists: false,
+            span: 0..11,
+        };
+        assert_eq!(check_duplicate_col_names(&table), Vec::new());
+    }
+
+    #[test]
+    fn flags_a_table_name_redefined_without_if_not_exists() {
+        let tables = vec![table("foo", false, 0, 10), table("foo", false, 20, 30)];
+        let diagnostics = check_duplicate_table_names(&tables);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].location, 20);
+        assert!(diagnostics[0].message.contains("foo"));
+    }
+
+    #[test]
+    fn suppresses_a_redefinition_guarded_by_if_not_exists() {
+        let tables = vec![table("foo", false, 0, 10), table("foo", true, 20, 30)];
+        assert_eq!(check_duplicate_table_names(&tables), Vec::new());
+    }
+
+    #[test]
+    fn does_not_flag_distinct_table_names() {
+        let tables = vec![table("foo", false, 0, 10), table("bar", false, 20, 30)];
+        assert_eq!(check_duplicate_table_names(&tables), Vec::new());
+    }
+
+    #[test]
+    fn duplicate_col_names_lint_stamps_its_configured_severity() {
+        let lint = DuplicateColNamesLint {
+            enabled: true,
+            severity: Severity::Warn,
+        };
+        let table = Table {
+            name: "widgets".to_string(),
+            columns: vec![col("a", 0, 5), col("a", 6, 11)],
+            if_not_exists: false,
+            span: 0..11,
+        };
+        let diagnostics = lint.check_table(&table);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warn);
+    }
+
+    #[test]
+    fn table_name_length_lint_reads_its_configured_min_length() {
+        let lint = TableNameLengthLint {
+            enabled: true,
+            severity: Severity::Error,
+            min_name_length: 20,
+        };
+        let t = table("widgets", false, 0, 10);
+        let diagnostics = lint.check_table(&t);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+
+        let lax_lint = TableNameLengthLint {
+            enabled: true,
+            severity: Severity::Error,
+            min_name_length: 1,
+        };
+        assert_eq!(lax_lint.check_table(&t), Vec::new());
+    }
+
+    #[test]
+    fn duplicate_table_names_lint_stamps_its_configured_severity() {
+        let lint = DuplicateTableNamesLint {
+            enabled: true,
+            severity: Severity::Warn,
+        };
+        let tables = vec![table("foo", false, 0, 10), table("foo", false, 20, 30)];
+        let diagnostics = lint.check_script(&tables);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Severity::Warn);
+    }
+}
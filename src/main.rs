@@ -1,153 +1,110 @@
-use std::borrow::Borrow;
-use std::collections::{HashMap, HashSet};
-use std::fmt::Error;
-use nom::branch::alt;
-use nom::bytes::complete::{is_a, is_not, tag, tag_no_case, take_until};
-use nom::combinator::opt;
-use nom::IResult;
-use nom::multi::separated_list0;
+mod cache;
+mod config;
+mod diagnostic;
+mod linter;
+mod parser;
+mod span;
 
-fn main() -> std::result::Result<(), BoxError> {
-    // First parse the table to make sure that it has the correct syntax
-    let (_, table) = parse_table("CREATE TABLE table (my_date Date, my_string String)")?;
-    // Run linters to catch any errors
-    let linters: Vec<fn(&Table) -> Option<String>> = vec![check_duplicate_col_names, check_table_name_is_not_short];
-    let mut did_find_issue = false;
-    for linter in &linters {
-        match linter(&table) {
-            Some(err) => {
-                did_find_issue = true;
-                println!("encountered error:\n\n{}", err)
-            }
-            None => {}
-        }
-    }
-    if !did_find_issue {
-        println!("Congrats! Your table looks fine")
-    }
-    Ok(())
-}
-
-/// Type-erased errors.
-pub type BoxError = std::boxed::Box<
-    dyn std::error::Error // must implement Error to satisfy ?
-        + std::marker::Send // needed for threads
-        + std::marker::Sync, // needed for threads
->;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Table {
-    name: String,
-    columns: Vec<Col>,
-    if_not_exists: bool,
-}
+use cache::LintCache;
+use config::Config;
+use diagnostic::{apply_suggestions, to_absolute, to_relative, Diagnostic};
+use parser::{parse_error_location, parse_script};
+use span::format_location;
 
-fn parse_table(input: &str) -> IResult<&str, Table> {
-    let (input, _) = tag_no_case("create table ")(input)?;
-    let (input, if_not_exists_str) = opt(tag_no_case("if not exists"))(input)?;
-    let if_not_exists = match if_not_exists_str {
-        Some(_) => true,
-        _ => false,
-    };
-    let (input, name) = take_until(" ")(input)?;
-    let (input, _) = tag(" ")(input)?;
-    let (input, _) = tag("(")(input)?;
-    let (input, cols) = separated_list0(tag(", "), parse_col)(input)?;
-    let (input, _) = tag(")")(input)?;
-    Ok((
-        input,
-        Table {
-            name: name.to_string(),
-            columns: cols,
-            if_not_exists,
-        },
-    ))
-}
+fn main() -> std::result::Result<(), BoxError> {
+    let args: Vec<String> = std::env::args().collect();
+    let fix = args.iter().any(|arg| arg == "--fix");
+    let cache_path: Option<PathBuf> = args
+        .iter()
+        .position(|arg| arg == "--cache")
+        .and_then(|i| args.get(i + 1))
+        .map(PathBuf::from);
+    let file = "schema.sql";
 
-#[derive(Debug, Clone, PartialEq)]
-pub struct Col {
-    name: String,
-    col_type: String,
-}
+    let config = Config::load(Path::new("clicklint.toml"))?;
+    let lints = config.build_lints();
+    let cache = cache_path.as_deref().map(LintCache::open).transpose()?;
 
-fn parse_col(input: &str) -> IResult<&str, Col> {
-    let (input, name) = take_until(" ")(input)?;
-    let (input, _) = is_a(" \t\r\n")(input)?;
-    let (input, col_type) = alt((tag("Date"), tag("String")))(input)?;
-    Ok((
-        input,
-        Col {
-            name: name.to_string(),
-            col_type: col_type.to_string(),
-        },
-    ))
-}
-
-fn check_duplicate_col_names(t: &Table) -> Option<String> {
-    let mut errors = "".to_string();
-    let mut col_names = HashMap::new();
-    for col in t.columns.iter() {
-        *col_names.entry(col.name.clone()).or_insert(0) += 1;
-    }
-    for entry in &col_names {
-        if *entry.1 > 1 {
-            errors += &format!("Duplicated column {} was encountered {} times.\n", entry.0, entry.1).to_owned()
+    let source = "CREATE TABLE table (my_date Date, my_string String); \
+        CREATE TABLE IF NOT EXISTS other_table (id String)";
+    // First parse the script to make sure every statement has correct syntax
+    let tables = match parse_script(source) {
+        Ok(("", tables)) => tables,
+        Ok((rest, _)) => {
+            let location = source.len() - rest.len();
+            println!("{}", format_location(file, source, location, "failed to parse schema"));
+            return Ok(());
         }
-    }
-    if errors.len() == 0 {
-        return None
-    }
-    Some(errors.to_string())
-}
+        Err(err) => {
+            let location = parse_error_location(source, &err).unwrap_or(0);
+            println!("{}", format_location(file, source, location, "failed to parse schema"));
+            return Ok(());
+        }
+    };
+
+    // Run every enabled lint's per-table check against each statement,
+    // short-circuiting through the cache when the statement's text is
+    // unchanged from a previous run.
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for table in &tables {
+        let statement_source = &source[table.span.clone()];
+        let statement_hash = cache::hash_statement(statement_source);
+        let cached = cache.as_ref().and_then(|c| c.get(&statement_hash).ok().flatten());
 
-fn check_table_name_is_not_short(t: &Table) -> Option<String> {
-    const MIN_LENGTH: usize = 5;
-    if t.name.len() < MIN_LENGTH  {
-        return Some(format!("Your table name '{}' is too short. We recommend at least {} characters." ,t.name, MIN_LENGTH));
+        let table_diagnostics = match cached {
+            // Cached diagnostics are stored relative to the statement's own
+            // start (see the `None` arm below), so they have to be rebased
+            // onto this statement's current absolute position before use -
+            // otherwise a cache hit on identical text at a different script
+            // offset would report (and, with `--fix`, apply suggestions at)
+            // the wrong location.
+            Some(diagnostics) => to_absolute(&diagnostics, table.span.start),
+            None => {
+                let fresh: Vec<Diagnostic> = lints
+                    .iter()
+                    .filter(|lint| lint.enabled())
+                    .flat_map(|lint| lint.check_table(table))
+                    .collect();
+                if let Some(cache) = &cache {
+                    cache.put(&statement_hash, &to_relative(&fresh, table.span.start))?;
+                }
+                fresh
+            }
+        };
+        diagnostics.extend(table_diagnostics);
     }
-    None
-}
 
+    // Cross-statement checks need the whole script, so they bypass the
+    // per-statement cache entirely.
+    diagnostics.extend(
+        lints
+            .iter()
+            .filter(|lint| lint.enabled())
+            .flat_map(|lint| lint.check_script(&tables)),
+    );
 
-#[cfg(test)]
-mod test {
-    use crate::{parse_col, parse_table, Col, Table};
+    if diagnostics.is_empty() {
+        println!("Congrats! Your table looks fine");
+        return Ok(());
+    }
 
-    #[test]
-    fn test_parse_col() {
-        assert_eq!(
-            parse_col("name Date"),
-            Ok((
-                "",
-                Col {
-                    name: "name".to_string(),
-                    col_type: "Date".to_string()
-                }
-            ))
-        )
+    for diagnostic in &diagnostics {
+        println!("encountered error:\n\n{}", diagnostic.format(file, source))
     }
 
-    #[test]
-    fn test_parse_table() {
-        assert_eq!(
-            parse_table("CREATE TABLE table (my_date Date, my_string String)"),
-            Ok((
-                "",
-                Table {
-                    name: "table".to_string(),
-                    columns: vec!(
-                        Col {
-                            name: "my_date".to_string(),
-                            col_type: "Date".to_string(),
-                        },
-                        Col {
-                            name: "my_string".to_string(),
-                            col_type: "String".to_string(),
-                        },
-                    ),
-                    if_not_exists: false
-                }
-            ))
-        )
+    if fix {
+        let fixed = apply_suggestions(source, &diagnostics);
+        println!("\nfixed source:\n\n{}", fixed);
     }
+
+    Ok(())
 }
+
+/// Type-erased errors.
+pub type BoxError = std::boxed::Box<
+    dyn std::error::Error // must implement Error to satisfy ?
+        + std::marker::Send // needed for threads
+        + std::marker::Sync, // needed for threads
+>;
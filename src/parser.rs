@@ -0,0 +1,209 @@
+use std::ops::Range;
+
+use nom::branch::alt;
+use nom::bytes::complete::{is_a, tag, tag_no_case, take_until};
+use nom::character::complete::{multispace0, multispace1};
+use nom::combinator::opt;
+use nom::sequence::tuple;
+use nom::IResult;
+
+/// Byte offset of `sub` within `original`, assuming `sub` is a subslice of
+/// `original` (as produced by nom combinators slicing through the input).
+fn offset(original: &str, sub: &str) -> usize {
+    sub.as_ptr() as usize - original.as_ptr() as usize
+}
+
+/// Byte offset in `source` where `nom` gave up, if the failure carries a
+/// remaining-input slice we can locate.
+pub fn parse_error_location(source: &str, err: &nom::Err<nom::error::Error<&str>>) -> Option<usize> {
+    match err {
+        nom::Err::Error(e) | nom::Err::Failure(e) => Some(offset(source, e.input)),
+        nom::Err::Incomplete(_) => None,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub name: String,
+    pub columns: Vec<Col>,
+    pub if_not_exists: bool,
+    pub span: Range<usize>,
+}
+
+pub fn parse_table(input: &str) -> IResult<&str, Table> {
+    let start = input;
+    let (input, _) = tag_no_case("create table ")(input)?;
+    // The trailing whitespace after the tag is part of this `opt`, not left
+    // for `take_until(" ")` below to skip - otherwise `take_until` would
+    // match zero bytes right after "exists" and `name` would be empty.
+    let (input, if_not_exists_str) = opt(tuple((tag_no_case("if not exists"), multispace1)))(input)?;
+    let if_not_exists = if_not_exists_str.is_some();
+    let (input, name) = take_until(" ")(input)?;
+    let (input, _) = tag(" ")(input)?;
+    let (mut input, _) = tag("(")(input)?;
+
+    // `parse_col` reports its span relative to its own slice, so rebase it
+    // onto `start` (the table's own beginning) the same way `parse_script`
+    // rebases whole tables onto the script.
+    let mut cols = Vec::new();
+    if !input.starts_with(')') {
+        loop {
+            let col_start = input;
+            let (rest, mut col) = parse_col(input)?;
+            let col_offset = offset(start, col_start);
+            col.span = (col.span.start + col_offset)..(col.span.end + col_offset);
+            cols.push(col);
+            input = rest;
+
+            let (rest, separator) = opt(tag(", "))(input)?;
+            input = rest;
+            if separator.is_none() {
+                break;
+            }
+        }
+    }
+
+    let (input, _) = tag(")")(input)?;
+    let end = offset(start, input);
+    Ok((
+        input,
+        Table {
+            name: name.to_string(),
+            columns: cols,
+            if_not_exists,
+            span: 0..end,
+        },
+    ))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Col {
+    pub name: String,
+    pub col_type: String,
+    pub span: Range<usize>,
+}
+
+/// Parses a `;`-separated sequence of `CREATE TABLE` statements, e.g. the
+/// contents of a whole schema file. Every `Table`/`Col` span is rebased to
+/// be an offset into `input` as a whole, rather than into its own
+/// statement, so callers can report positions directly against the script.
+pub fn parse_script(input: &str) -> IResult<&str, Vec<Table>> {
+    let mut tables = Vec::new();
+    let (mut remaining, _) = multispace0(input)?;
+    while !remaining.is_empty() {
+        let stmt_offset = offset(input, remaining);
+        let (rest, mut table) = parse_table(remaining)?;
+        rebase(&mut table, stmt_offset);
+        tables.push(table);
+
+        let (rest, _) = multispace0(rest)?;
+        let (rest, terminator) = opt(tag(";"))(rest)?;
+        let (rest, _) = multispace0(rest)?;
+        remaining = rest;
+        if terminator.is_none() {
+            break;
+        }
+    }
+    Ok((remaining, tables))
+}
+
+fn rebase(table: &mut Table, stmt_offset: usize) {
+    table.span = (table.span.start + stmt_offset)..(table.span.end + stmt_offset);
+    for col in table.columns.iter_mut() {
+        col.span = (col.span.start + stmt_offset)..(col.span.end + stmt_offset);
+    }
+}
+
+pub fn parse_col(input: &str) -> IResult<&str, Col> {
+    let start = input;
+    let (input, name) = take_until(" ")(input)?;
+    let (input, _) = is_a(" \t\r\n")(input)?;
+    let (input, col_type) = alt((tag("Date"), tag("String")))(input)?;
+    let end = offset(start, input);
+    Ok((
+        input,
+        Col {
+            name: name.to_string(),
+            col_type: col_type.to_string(),
+            span: offset(start, start)..end,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_col() {
+        assert_eq!(
+            parse_col("name Date"),
+            Ok((
+                "",
+                Col {
+                    name: "name".to_string(),
+                    col_type: "Date".to_string(),
+                    span: 0..9,
+                }
+            ))
+        )
+    }
+
+    #[test]
+    fn test_parse_table() {
+        assert_eq!(
+            parse_table("CREATE TABLE table (my_date Date, my_string String)"),
+            Ok((
+                "",
+                Table {
+                    name: "table".to_string(),
+                    columns: vec!(
+                        Col {
+                            name: "my_date".to_string(),
+                            col_type: "Date".to_string(),
+                            span: 20..32,
+                        },
+                        Col {
+                            name: "my_string".to_string(),
+                            col_type: "String".to_string(),
+                            span: 34..50,
+                        },
+                    ),
+                    if_not_exists: false,
+                    span: 0..51,
+                }
+            ))
+        )
+    }
+
+    #[test]
+    fn test_parse_table_if_not_exists() {
+        assert_eq!(
+            parse_table("CREATE TABLE IF NOT EXISTS foo (a Date)"),
+            Ok((
+                "",
+                Table {
+                    name: "foo".to_string(),
+                    columns: vec!(Col {
+                        name: "a".to_string(),
+                        col_type: "Date".to_string(),
+                        span: 32..38,
+                    }),
+                    if_not_exists: true,
+                    span: 0..39,
+                }
+            ))
+        )
+    }
+
+    #[test]
+    fn test_parse_script_rebases_spans_across_statements() {
+        let (rest, tables) = parse_script("CREATE TABLE foo (abc Date); CREATE TABLE bar (xyz String)").unwrap();
+        assert_eq!(rest, "");
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].name, "foo");
+        assert_eq!(tables[0].span, 0..27);
+        assert_eq!(tables[1].name, "bar");
+        assert_eq!(tables[1].span, 29..58);
+    }
+}
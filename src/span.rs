@@ -0,0 +1,44 @@
+/// Converts a 0-based byte offset into `source` to a 1-based `(line, column)`
+/// pair by scanning the newlines that precede it, the way `toml`'s `Span`
+/// machinery reports positions back to the user.
+pub fn byte_to_line_col(source: &str, byte_offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut col = 1;
+    for ch in source[..byte_offset.min(source.len())].chars() {
+        if ch == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+    (line, col)
+}
+
+/// Formats `message` as `file:line:col: message`, the conventional
+/// compiler-style location prefix.
+pub fn format_location(file: &str, source: &str, byte_offset: usize, message: &str) -> String {
+    let (line, col) = byte_to_line_col(source, byte_offset);
+    format!("{}:{}:{}: {}", file, line, col, message)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn first_line_first_column() {
+        assert_eq!(byte_to_line_col("abc", 0), (1, 1));
+    }
+
+    #[test]
+    fn after_a_newline() {
+        assert_eq!(byte_to_line_col("ab\ncd", 3), (2, 1));
+        assert_eq!(byte_to_line_col("ab\ncd", 4), (2, 2));
+    }
+
+    #[test]
+    fn counts_multiple_newlines() {
+        assert_eq!(byte_to_line_col("a\nb\nc", 4), (3, 1));
+    }
+}